@@ -0,0 +1,365 @@
+//! A typed, discoverable control surface over the parsed GenICam node
+//! graph, modeled on libcamera's `ControlInfoMap`.
+//!
+//! Without this layer, callers have to walk raw [`NodeData`] out of
+//! [`NodeStore`] by [`NodeId`] and replicate register math by hand.
+//! [`ControlList`] instead lets them enumerate every settable feature as a
+//! [`ControlInfo`] and get/set it by a well-known key such as
+//! `"ExposureTime"` or `"Gain"`.
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::parser::node_store::{NodeId, NodeStore};
+use crate::parser::register_description::NodeData;
+use crate::parser::{AccessMode, CachingMode};
+
+/// A port a register's address space can be read from and written to.
+/// Implemented by the device-specific transport (USB3 or GigE control
+/// channel) that owns the physical connection.
+pub trait ControlPort {
+    fn read(&mut self, address: u64, len: usize) -> io::Result<Vec<u8>>;
+
+    fn write(&mut self, address: u64, data: &[u8]) -> io::Result<()>;
+}
+
+/// Lets a `&mut impl ControlPort` be handed to [`ControlList::new`]
+/// directly, so callers that only have a borrow of their real port (e.g.
+/// one also used for other purposes) don't have to wrap it in anything.
+impl<T: ControlPort + ?Sized> ControlPort for &mut T {
+    fn read(&mut self, address: u64, len: usize) -> io::Result<Vec<u8>> {
+        (**self).read(address, len)
+    }
+
+    fn write(&mut self, address: u64, data: &[u8]) -> io::Result<()> {
+        (**self).write(address, data)
+    }
+}
+
+/// The value type a control holds, mirroring the GenICam node kinds that
+/// can be exposed as a control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlType {
+    Integer,
+    Float,
+    Bool,
+    Enum,
+    String,
+    Command,
+}
+
+/// A control's value, boxed so [`ControlList::get`] can return any of the
+/// [`ControlType`] variants through one call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlValue {
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Enum(i64),
+    String(String),
+    Command,
+}
+
+/// Static description of one settable feature: its type, range, unit, and
+/// current accessibility.
+#[derive(Debug, Clone)]
+pub struct ControlInfo {
+    pub name: String,
+    pub control_type: ControlType,
+    pub min: Option<ControlValue>,
+    pub max: Option<ControlValue>,
+    pub step: Option<ControlValue>,
+    pub unit: Option<String>,
+    pub access_mode: AccessMode,
+    /// The control's value at the time [`ControlList::enumerate`] was
+    /// called, or `None` if reading it failed (e.g. it is write-only, or
+    /// the port returned an error).
+    pub current_value: Option<ControlValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlError {
+    NotFound,
+    WrongType,
+    AccessDenied,
+    Io,
+}
+
+pub type ControlResult<T> = Result<T, ControlError>;
+
+struct CacheEntry {
+    value: ControlValue,
+}
+
+/// A discoverable, strongly-typed view over a device's node graph.
+///
+/// `ControlList` holds no device state of its own beyond a per-node value
+/// cache; all register access goes through the [`ControlPort`] supplied at
+/// construction, honoring each node's [`CachingMode`].
+pub struct ControlList<'a, P: ControlPort> {
+    store: &'a mut NodeStore,
+    port: P,
+    cache: HashMap<NodeId, CacheEntry>,
+}
+
+impl<'a, P: ControlPort> ControlList<'a, P> {
+    #[must_use]
+    pub fn new(store: &'a mut NodeStore, port: P) -> Self {
+        Self {
+            store,
+            port,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Enumerates every node in the store that can be exposed as a
+    /// control, i.e. every `Integer`/`Float`/`Boolean`/`Enumeration`/
+    /// `String`/`Command` node, together with its current value (best
+    /// effort: a control whose read fails just gets `current_value: None`
+    /// rather than failing the whole enumeration).
+    pub fn enumerate(&mut self) -> Vec<ControlInfo> {
+        let named: Vec<(String, NodeId)> = self
+            .store
+            .iter_named()
+            .map(|(name, id)| (name.to_string(), id))
+            .collect();
+
+        named
+            .into_iter()
+            .filter_map(|(name, id)| self.control_info(&name, id))
+            .collect()
+    }
+
+    fn control_info(&mut self, name: &str, id: NodeId) -> Option<ControlInfo> {
+        let data = self.store.node_opt(id)?;
+        let (control_type, access_mode, unit, min, max, step) = match data {
+            NodeData::Integer(n) => (
+                ControlType::Integer,
+                n.access_mode(),
+                None,
+                n.min().map(ControlValue::Integer),
+                n.max().map(ControlValue::Integer),
+                n.step().map(ControlValue::Integer),
+            ),
+            NodeData::Float(n) => (
+                ControlType::Float,
+                n.access_mode(),
+                n.unit().map(str::to_string),
+                n.min().map(ControlValue::Float),
+                n.max().map(ControlValue::Float),
+                n.step().map(ControlValue::Float),
+            ),
+            NodeData::Boolean(n) => (ControlType::Bool, n.access_mode(), None, None, None, None),
+            NodeData::Enumeration(n) => {
+                (ControlType::Enum, n.access_mode(), None, None, None, None)
+            }
+            NodeData::StringReg(n) => {
+                (ControlType::String, n.access_mode(), None, None, None, None)
+            }
+            NodeData::String(n) => {
+                (ControlType::String, n.access_mode(), None, None, None, None)
+            }
+            NodeData::Command(n) => {
+                (ControlType::Command, n.access_mode(), None, None, None, None)
+            }
+        };
+
+        let current_value = self.get(name).ok();
+
+        Some(ControlInfo {
+            name: name.to_string(),
+            control_type,
+            min,
+            max,
+            step,
+            unit,
+            access_mode,
+            current_value,
+        })
+    }
+
+    /// Reads the current value of the control named `key`, resolving its
+    /// address (including `SwissKnife`/`pIndex` expressions) and reading
+    /// through the device port, or returning the cached value if the
+    /// node's [`CachingMode`] allows it.
+    pub fn get(&mut self, key: &str) -> ControlResult<ControlValue> {
+        let id = self.store.id_by_name(key);
+        let data = self.store.node_opt(id).ok_or(ControlError::NotFound)?;
+
+        // A plain (non-register-backed) string value needs no port
+        // round-trip at all.
+        if let NodeData::String(n) = data {
+            return Ok(ControlValue::String(n.value().to_string()));
+        }
+
+        if let Some(cached) = self.cache.get(&id) {
+            if Self::cacheable_on_read(data) {
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let value = self.read_through(id)?;
+        if Self::cacheable_on_read(data) {
+            self.cache.insert(
+                id,
+                CacheEntry {
+                    value: value.clone(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Writes `value` to the control named `key`, after checking its
+    /// [`AccessMode`] allows writing.
+    pub fn set(&mut self, key: &str, value: ControlValue) -> ControlResult<()> {
+        let id = self.store.id_by_name(key);
+
+        // A plain (non-register-backed) string value is just updated
+        // in-store; there is no device side to write through to.
+        if matches!(self.store.node_opt(id), Some(NodeData::String(_))) {
+            let ControlValue::String(s) = value else {
+                return Err(ControlError::WrongType);
+            };
+            let NodeData::String(n) = self.store.node_mut(id).ok_or(ControlError::NotFound)? else {
+                unreachable!("checked above");
+            };
+            if n.access_mode() == AccessMode::RO {
+                return Err(ControlError::AccessDenied);
+            }
+            n.set_value(s);
+            return Ok(());
+        }
+
+        self.write_through(id, &value)?;
+        self.cache.insert(id, CacheEntry { value });
+        Ok(())
+    }
+
+    fn cacheable_on_read(data: &NodeData) -> bool {
+        let Some(register_base) = data.as_register_base() else {
+            return false;
+        };
+        matches!(
+            register_base.cacheable(),
+            CachingMode::WriteThrough | CachingMode::WriteAround
+        )
+    }
+
+    fn read_through(&mut self, id: NodeId) -> ControlResult<ControlValue> {
+        let data = self.store.node(id);
+        let register_base = data.as_register_base().ok_or(ControlError::WrongType)?;
+
+        if register_base.access_mode() == AccessMode::WO {
+            return Err(ControlError::AccessDenied);
+        }
+
+        let address = crate::register_address::resolve(register_base, self.store, &mut self.port)
+            .map_err(|_| ControlError::Io)?;
+        let len = register_base.length().imm().unwrap_or(4) as usize;
+        let raw = self
+            .port
+            .read(address, len)
+            .map_err(|_| ControlError::Io)?;
+
+        Ok(decode_raw(data, &raw))
+    }
+
+    fn write_through(&mut self, id: NodeId, value: &ControlValue) -> ControlResult<()> {
+        let data = self.store.node(id);
+        let register_base = data.as_register_base().ok_or(ControlError::WrongType)?;
+
+        if register_base.access_mode() == AccessMode::RO {
+            return Err(ControlError::AccessDenied);
+        }
+
+        let address = crate::register_address::resolve(register_base, self.store, &mut self.port)
+            .map_err(|_| ControlError::Io)?;
+        let len = register_base.length().imm().unwrap_or(4) as usize;
+        let raw = encode_raw(data, value, len)?;
+        self.port
+            .write(address, &raw)
+            .map_err(|_| ControlError::Io)
+    }
+}
+
+fn decode_raw(data: &NodeData, raw: &[u8]) -> ControlValue {
+    match data {
+        NodeData::Float(_) => ControlValue::Float(be_f64(raw)),
+        NodeData::Boolean(_) => ControlValue::Bool(raw.first().copied().unwrap_or(0) != 0),
+        NodeData::Enumeration(_) => ControlValue::Enum(be_i64(raw)),
+        NodeData::StringReg(_) => ControlValue::String(
+            String::from_utf8_lossy(raw)
+                .trim_end_matches('\0')
+                .to_string(),
+        ),
+        NodeData::Command(_) => ControlValue::Command,
+        NodeData::Integer(_) | NodeData::String(_) => ControlValue::Integer(be_i64(raw)),
+    }
+}
+
+fn be_i64(raw: &[u8]) -> i64 {
+    let mut buf = [0u8; 8];
+    let start = 8usize.saturating_sub(raw.len());
+    buf[start..].copy_from_slice(&raw[..raw.len().min(8)]);
+    i64::from_be_bytes(buf)
+}
+
+/// A `Float` register can be described as either 4 or 8 bytes wide;
+/// anything else is a malformed description, so it reads as `0.0` rather
+/// than panicking.
+fn be_f64(raw: &[u8]) -> f64 {
+    match raw.len() {
+        4 => raw
+            .try_into()
+            .map(f32::from_be_bytes)
+            .map(f64::from)
+            .unwrap_or(0.0),
+        8 => raw.try_into().map(f64::from_be_bytes).unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
+
+/// Encodes `data`/`value` into exactly `len` bytes for integer, enum,
+/// string and command registers, the same register length `read_through`
+/// sizes its reads to, so a register described as e.g. 4 bytes doesn't
+/// get an 8-byte write spilling into whatever follows it in the device's
+/// address space. A `Float` is encoded as 4 bytes when `len` is 4 and as
+/// 8 bytes otherwise; a `Boolean` is always a single byte.
+fn encode_raw(data: &NodeData, value: &ControlValue, len: usize) -> ControlResult<Vec<u8>> {
+    Ok(match (data, value) {
+        (NodeData::Integer(_) | NodeData::Enumeration(_), ControlValue::Integer(v))
+        | (NodeData::Enumeration(_), ControlValue::Enum(v)) => be_i64_sized(*v, len),
+        (NodeData::Float(_), ControlValue::Float(v)) => be_f64_sized(*v, len),
+        (NodeData::Boolean(_), ControlValue::Bool(v)) => vec![u8::from(*v)],
+        (NodeData::StringReg(_), ControlValue::String(s)) => {
+            let mut bytes = s.as_bytes().to_vec();
+            bytes.resize(len, 0);
+            bytes
+        }
+        // A command is triggered by writing a non-zero value (always `1`)
+        // to its register; only `ControlValue::Command` is accepted.
+        (NodeData::Command(_), ControlValue::Command) => be_i64_sized(1, len),
+        _ => return Err(ControlError::WrongType),
+    })
+}
+
+fn be_i64_sized(v: i64, len: usize) -> Vec<u8> {
+    let full = v.to_be_bytes();
+    if len <= 8 {
+        full[8 - len..].to_vec()
+    } else {
+        let mut out = vec![0u8; len - 8];
+        out.extend_from_slice(&full);
+        out
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn be_f64_sized(v: f64, len: usize) -> Vec<u8> {
+    if len == 4 {
+        (v as f32).to_be_bytes().to_vec()
+    } else {
+        v.to_be_bytes().to_vec()
+    }
+}
@@ -3,6 +3,7 @@ use string_interner::{StringInterner, Symbol};
 use super::register_description::NodeData;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeId(u32);
 
 impl Symbol for NodeId {
@@ -49,6 +50,10 @@ impl NodeStore {
         self.store.get(id.to_usize())?.as_ref()
     }
 
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut NodeData> {
+        self.store.get_mut(id.to_usize())?.as_mut()
+    }
+
     pub(super) fn store_node(&mut self, id: NodeId, data: NodeData) {
         let id = id.to_usize();
         if self.store.len() <= id {
@@ -57,10 +62,79 @@ impl NodeStore {
         debug_assert!(self.store[id].is_none());
         self.store[id] = Some(data);
     }
+
+    /// Iterates over every interned name, paired with the [`NodeId`] it
+    /// resolves to.
+    pub fn iter_named(&self) -> impl Iterator<Item = (&str, NodeId)> {
+        self.interner.into_iter().map(|(id, s)| (s, id))
+    }
 }
 
 impl Default for NodeStore {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::{NodeData, NodeStore, StringInterner};
+
+    /// The on-disk shape: the interner is flattened to a `Vec<String>`
+    /// ordered by `NodeId`, so re-interning each name in order on
+    /// deserialization reproduces the exact same `NodeId` mapping
+    /// `id_by_name` would have assigned.
+    impl Serialize for NodeStore {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let names: Vec<&str> = self.interner.into_iter().map(|(_, s)| s).collect();
+            let mut state = serializer.serialize_struct("NodeStore", 2)?;
+            state.serialize_field("names", &names)?;
+            state.serialize_field("store", &self.store)?;
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for NodeStore {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            #[derive(serde::Deserialize)]
+            struct Raw {
+                names: Vec<String>,
+                store: Vec<Option<NodeData>>,
+            }
+
+            let raw = Raw::deserialize(deserializer)?;
+            let mut interner = StringInterner::new();
+            for name in raw.names {
+                interner.get_or_intern(name);
+            }
+
+            Ok(NodeStore {
+                interner,
+                store: raw.store,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::NodeStore;
+
+        #[test]
+        fn round_trip_preserves_id_by_name_mapping() {
+            let mut store = NodeStore::new();
+            let alpha = store.id_by_name("Alpha");
+            let beta = store.id_by_name("Beta");
+            let gamma = store.id_by_name("Gamma");
+
+            let json = serde_json::to_string(&store).unwrap();
+            let mut restored: NodeStore = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(restored.id_by_name("Alpha"), alpha);
+            assert_eq!(restored.id_by_name("Beta"), beta);
+            assert_eq!(restored.id_by_name("Gamma"), gamma);
+        }
+    }
 }
\ No newline at end of file
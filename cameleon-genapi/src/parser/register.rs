@@ -7,6 +7,7 @@ use super::{
 };
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegisterNode {
     attr_base: NodeAttributeBase,
     register_base: RegisterBase,
@@ -23,6 +24,11 @@ impl RegisterNode {
     pub fn register_base(&self) -> &RegisterBase {
         &self.register_base
     }
+
+    #[must_use]
+    pub fn access_mode(&self) -> super::AccessMode {
+        self.register_base.access_mode()
+    }
 }
 
 impl Parse for RegisterNode {
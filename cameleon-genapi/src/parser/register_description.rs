@@ -0,0 +1,202 @@
+//! The parsed node graph's payload types: every concrete node kind a
+//! [`super::node_store::NodeStore`] can hold, behind the single
+//! [`NodeData`] enum so the store can stay kind-agnostic.
+
+use super::node_base::{NodeAttributeBase, NodeBase};
+use super::register::RegisterNode;
+use super::register_base::RegisterBase;
+use super::AccessMode;
+
+/// Any node kind the parser can produce, keyed by [`super::node_store::NodeId`]
+/// in the store.
+///
+/// `feature = "serde"` round-trips this enum, which in turn requires
+/// [`RegisterBase`] and [`NodeAttributeBase`] (and everything reachable
+/// from them) to derive `Serialize`/`Deserialize` too.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeData {
+    Integer(IntegerNode),
+    Float(FloatNode),
+    Boolean(BooleanNode),
+    Enumeration(EnumerationNode),
+    StringReg(RegisterNode),
+    String(StringNode),
+    Command(CommandNode),
+}
+
+impl NodeData {
+    /// Returns the [`RegisterBase`] backing this node, for the variants
+    /// that address actual device memory (everything but a plain
+    /// [`StringNode`] literal).
+    #[must_use]
+    pub(crate) fn as_register_base(&self) -> Option<&RegisterBase> {
+        match self {
+            Self::Integer(n) => Some(n.register_base()),
+            Self::Float(n) => Some(n.register_base()),
+            Self::Boolean(n) => Some(n.register_base()),
+            Self::Enumeration(n) => Some(n.register_base()),
+            Self::StringReg(n) => Some(n.register_base()),
+            Self::Command(n) => Some(n.register_base()),
+            Self::String(_) => None,
+        }
+    }
+}
+
+macro_rules! register_backed_node {
+    ($name:ident) => {
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name {
+            attr_base: NodeAttributeBase,
+            register_base: RegisterBase,
+        }
+
+        impl $name {
+            #[must_use]
+            pub fn node_base(&self) -> NodeBase {
+                NodeBase::new(&self.attr_base, &self.register_base.elem_base)
+            }
+
+            #[must_use]
+            pub fn register_base(&self) -> &RegisterBase {
+                &self.register_base
+            }
+
+            #[must_use]
+            pub fn access_mode(&self) -> AccessMode {
+                self.register_base.access_mode()
+            }
+        }
+    };
+}
+
+register_backed_node!(BooleanNode);
+register_backed_node!(EnumerationNode);
+register_backed_node!(CommandNode);
+
+/// An integer-valued register, optionally bounded by a `Min`/`Max`/`Inc`
+/// triple from its GenICam description.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntegerNode {
+    attr_base: NodeAttributeBase,
+    register_base: RegisterBase,
+    min: Option<i64>,
+    max: Option<i64>,
+    step: Option<i64>,
+}
+
+impl IntegerNode {
+    #[must_use]
+    pub fn node_base(&self) -> NodeBase {
+        NodeBase::new(&self.attr_base, &self.register_base.elem_base)
+    }
+
+    #[must_use]
+    pub fn register_base(&self) -> &RegisterBase {
+        &self.register_base
+    }
+
+    #[must_use]
+    pub fn access_mode(&self) -> AccessMode {
+        self.register_base.access_mode()
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<i64> {
+        self.min
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<i64> {
+        self.max
+    }
+
+    #[must_use]
+    pub fn step(&self) -> Option<i64> {
+        self.step
+    }
+}
+
+/// A float-valued register, optionally bounded by a `Min`/`Max`/`Inc`
+/// triple from its GenICam description.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatNode {
+    attr_base: NodeAttributeBase,
+    register_base: RegisterBase,
+    unit: Option<String>,
+    min: Option<f64>,
+    max: Option<f64>,
+    step: Option<f64>,
+}
+
+impl FloatNode {
+    #[must_use]
+    pub fn node_base(&self) -> NodeBase {
+        NodeBase::new(&self.attr_base, &self.register_base.elem_base)
+    }
+
+    #[must_use]
+    pub fn register_base(&self) -> &RegisterBase {
+        &self.register_base
+    }
+
+    #[must_use]
+    pub fn access_mode(&self) -> AccessMode {
+        self.register_base.access_mode()
+    }
+
+    #[must_use]
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    #[must_use]
+    pub fn step(&self) -> Option<f64> {
+        self.step
+    }
+}
+
+/// A plain string value node, not backed by a register (e.g. a
+/// `DeviceVendorName` literal pulled straight from the GenICam
+/// description rather than read off the device).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringNode {
+    attr_base: NodeAttributeBase,
+    value: String,
+    access_mode: AccessMode,
+}
+
+impl StringNode {
+    #[must_use]
+    pub fn node_base(&self) -> &NodeAttributeBase {
+        &self.attr_base
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+    }
+
+    #[must_use]
+    pub fn access_mode(&self) -> AccessMode {
+        self.access_mode
+    }
+}
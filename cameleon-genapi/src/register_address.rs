@@ -0,0 +1,76 @@
+//! Resolves a [`RegisterBase`]'s `AddressKind` list down to a single
+//! absolute address, evaluating `SwissKnife`/`pIndex` expressions against
+//! the node graph where needed.
+//!
+//! This is shared by [`crate::control::ControlList`] and anything else
+//! that needs to turn a parsed register description into bytes on the
+//! wire without re-deriving the addressing rules.
+
+use crate::control::ControlPort;
+use crate::parser::node_store::NodeStore;
+use crate::parser::register_node_elem::AddressKind;
+use crate::parser::{ImmOrPNode, RegisterBase};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveError;
+
+/// Sums every entry in `register_base.address_kinds()`, which is how the
+/// GenICam schema composes a final address out of a base `Address` plus
+/// any number of `pIndex` offsets.
+pub(crate) fn resolve<P: ControlPort>(
+    register_base: &RegisterBase,
+    store: &mut NodeStore,
+    port: &mut P,
+) -> Result<u64, ResolveError> {
+    let mut address = 0u64;
+    for kind in register_base.address_kinds() {
+        address += match kind {
+            AddressKind::Address(imm_or_pnode) => resolve_imm_or_pnode(imm_or_pnode, store, port)?,
+            AddressKind::IntSwissKnife(swiss_knife) => {
+                swiss_knife.evaluate(store).map_err(|_| ResolveError)? as u64
+            }
+            AddressKind::PIndex(p_index) => {
+                let offset = match p_index.offset() {
+                    Some(imm_or_pnode) => resolve_imm_or_pnode(imm_or_pnode, store, port)?,
+                    None => 0,
+                };
+                let index = resolve_register_value(p_index.p_index(), store, port)?;
+                offset + index
+            }
+        };
+    }
+    Ok(address)
+}
+
+fn resolve_imm_or_pnode<P: ControlPort>(
+    value: &ImmOrPNode<i64>,
+    store: &mut NodeStore,
+    port: &mut P,
+) -> Result<u64, ResolveError> {
+    match value {
+        ImmOrPNode::Imm(imm) => Ok(*imm as u64),
+        ImmOrPNode::PNode(node_id) => resolve_register_value(*node_id, store, port),
+    }
+}
+
+/// Reads the value of another node that participates in an address
+/// expression (e.g. the node a `pIndex` points at).
+fn resolve_register_value<P: ControlPort>(
+    node_id: crate::parser::node_store::NodeId,
+    store: &mut NodeStore,
+    port: &mut P,
+) -> Result<u64, ResolveError> {
+    let register_base = store
+        .node_opt(node_id)
+        .and_then(crate::parser::register_description::NodeData::as_register_base)
+        .ok_or(ResolveError)?;
+
+    let address = resolve(register_base, store, port)?;
+    let len = register_base.length().imm().unwrap_or(4) as usize;
+    let raw = port.read(address, len).map_err(|_| ResolveError)?;
+
+    let mut buf = [0u8; 8];
+    let start = 8usize.saturating_sub(raw.len());
+    buf[start..].copy_from_slice(&raw[..raw.len().min(8)]);
+    Ok(u64::from_be_bytes(buf))
+}
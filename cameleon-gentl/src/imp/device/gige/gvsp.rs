@@ -0,0 +1,266 @@
+//! GVSP (GigE Vision Streaming Protocol) receiver.
+//!
+//! A GVSP stream is a sequence of UDP datagrams, each tagged with a
+//! `block_id` identifying the frame they belong to and a packet format of
+//! either leader, payload, or trailer. Packets for a block can arrive out
+//! of order (and be lost), so they are reassembled per `block_id`, and
+//! [`GvspReceiver::recv_payload`] returns each completed payload to its
+//! caller. This module does not push payloads into the `u3v` streaming
+//! path's delivery channel itself.
+
+use std::collections::{HashMap, HashSet};
+use std::net::UdpSocket;
+
+use crate::GenTlResult;
+
+/// A single reassembled frame, keyed by its GVSP `block_id`. Shaped to
+/// match the payload the `u3v` stream handle pushes into its delivery
+/// channel, so both transports can feed the same consumer-facing queue.
+pub(crate) struct StreamPayload {
+    pub(crate) block_id: u64,
+    pub(crate) data: Vec<u8>,
+}
+
+const LEADER: u8 = 0x01;
+const TRAILER: u8 = 0x02;
+const PAYLOAD: u8 = 0x03;
+
+struct PendingBlock {
+    data: Vec<u8>,
+    /// Size of a full payload packet's fragment, learned from packet id
+    /// `0` (every payload packet is this size except the last, shorter
+    /// one), used to place out-of-order fragments at the right offset.
+    packet_size: Option<usize>,
+    /// Payload fragments that arrived before packet `0`, held back until
+    /// `packet_size` is known and their offset can be computed.
+    early_fragments: Vec<(u32, Vec<u8>)>,
+    /// Total payload byte count, carried by the leader packet.
+    payload_size: Option<usize>,
+    received_packet_ids: HashSet<u32>,
+    leader_seen: bool,
+    trailer_seen: bool,
+}
+
+impl PendingBlock {
+    fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            packet_size: None,
+            early_fragments: Vec::new(),
+            payload_size: None,
+            received_packet_ids: HashSet::new(),
+            leader_seen: false,
+            trailer_seen: false,
+        }
+    }
+
+    /// A block is complete only once the leader and trailer have both
+    /// been seen *and* every payload packet implied by `payload_size` /
+    /// `packet_size` has actually been received — the leader/trailer can
+    /// themselves arrive out of order, ahead of a still-in-flight payload
+    /// packet.
+    fn is_complete(&self) -> bool {
+        if !self.leader_seen || !self.trailer_seen {
+            return false;
+        }
+        let (Some(payload_size), Some(packet_size)) = (self.payload_size, self.packet_size) else {
+            return false;
+        };
+        if packet_size == 0 {
+            return false;
+        }
+        let expected_packets = payload_size.div_ceil(packet_size) as u32;
+        (0..expected_packets).all(|id| self.received_packet_ids.contains(&id))
+    }
+}
+
+/// A single parsed GVSP packet header, shared by leader/payload/trailer
+/// packets (the packet-format byte selects which fields are meaningful).
+struct GvspHeader {
+    block_id: u64,
+    packet_format: u8,
+    packet_id: u32,
+    /// Only meaningful for a leader packet: the total payload size, in
+    /// bytes, that this block's payload packets will add up to.
+    leader_payload_size: Option<u32>,
+}
+
+fn parse_header(buf: &[u8]) -> Option<GvspHeader> {
+    if buf.len() < 8 {
+        return None;
+    }
+    let block_id = u64::from(u16::from_be_bytes([buf[0], buf[1]]));
+    let packet_format = buf[4];
+    let packet_id = u32::from_be_bytes([0, buf[5], buf[6], buf[7]]);
+    let leader_payload_size = if packet_format == LEADER {
+        buf.get(8..12)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    } else {
+        None
+    };
+    Some(GvspHeader {
+        block_id,
+        packet_format,
+        packet_id,
+        leader_payload_size,
+    })
+}
+
+/// Reassembles GVSP packets arriving on `socket` into complete payloads,
+/// returned one at a time by [`Self::recv_payload`].
+pub(crate) struct GvspReceiver {
+    socket: UdpSocket,
+    pending: HashMap<u64, PendingBlock>,
+}
+
+impl GvspReceiver {
+    pub(crate) fn new(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Blocks on the socket until one full frame has been reassembled, or
+    /// an I/O error occurs.
+    pub(crate) fn recv_payload(&mut self) -> GenTlResult<StreamPayload> {
+        let mut buf = [0u8; 1500];
+        loop {
+            let n = self.socket.recv(&mut buf).map_err(|_| crate::GenTlError::Io)?;
+            let Some(header) = parse_header(&buf[..n]) else {
+                continue;
+            };
+            if let Some(payload) = Self::apply_packet(&mut self.pending, &header, &buf[..n]) {
+                return Ok(payload);
+            }
+        }
+    }
+
+    /// Folds one parsed packet into the block it belongs to, returning
+    /// the reassembled payload once that block is complete. Split out of
+    /// `recv_payload` so the reassembly logic can be unit-tested without
+    /// a real socket.
+    fn apply_packet(
+        pending: &mut HashMap<u64, PendingBlock>,
+        header: &GvspHeader,
+        raw: &[u8],
+    ) -> Option<StreamPayload> {
+        let block = pending.entry(header.block_id).or_insert_with(PendingBlock::new);
+
+        match header.packet_format {
+            LEADER => {
+                block.leader_seen = true;
+                if let Some(size) = header.leader_payload_size {
+                    block.payload_size = Some(size as usize);
+                }
+            }
+            TRAILER => block.trailer_seen = true,
+            PAYLOAD => {
+                // Packets may arrive out of order; place each fragment at
+                // its declared offset (using the *fixed* packet size
+                // learned from packet 0) rather than appending blindly
+                // or using this packet's own, possibly-shorter, length.
+                // Fragments arriving before packet 0 are held back until
+                // that size is known.
+                let fragment = &raw[8..];
+                if header.packet_id == 0 {
+                    block.packet_size = Some(fragment.len());
+                }
+                match block.packet_size {
+                    Some(packet_size) => {
+                        let mut fragments = std::mem::take(&mut block.early_fragments);
+                        fragments.push((header.packet_id, fragment.to_vec()));
+                        for (packet_id, fragment) in fragments {
+                            let offset = packet_id as usize * packet_size;
+                            if block.data.len() < offset + fragment.len() {
+                                block.data.resize(offset + fragment.len(), 0);
+                            }
+                            block.data[offset..offset + fragment.len()].copy_from_slice(&fragment);
+                        }
+                    }
+                    None => block
+                        .early_fragments
+                        .push((header.packet_id, fragment.to_vec())),
+                }
+                block.received_packet_ids.insert(header.packet_id);
+            }
+            _ => return None,
+        }
+
+        if block.is_complete() {
+            let block = pending.remove(&header.block_id).unwrap();
+            return Some(StreamPayload {
+                block_id: header.block_id,
+                data: block.data,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leader(block_id: u16, payload_size: u32) -> Vec<u8> {
+        let mut packet = vec![0u8; 12];
+        packet[0..2].copy_from_slice(&block_id.to_be_bytes());
+        packet[4] = LEADER;
+        packet[8..12].copy_from_slice(&payload_size.to_be_bytes());
+        packet
+    }
+
+    fn trailer(block_id: u16) -> Vec<u8> {
+        let mut packet = vec![0u8; 8];
+        packet[0..2].copy_from_slice(&block_id.to_be_bytes());
+        packet[4] = TRAILER;
+        packet
+    }
+
+    fn payload_packet(block_id: u16, packet_id: u32, fragment: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0u8; 8];
+        packet[0..2].copy_from_slice(&block_id.to_be_bytes());
+        packet[4] = PAYLOAD;
+        packet[5..8].copy_from_slice(&packet_id.to_be_bytes()[1..]);
+        packet.extend_from_slice(fragment);
+        packet
+    }
+
+    #[test]
+    fn reassembles_uneven_sized_payload_out_of_order() {
+        // Two packets of 4 bytes each, plus a short final 2-byte packet:
+        // exercises both the fixed packet-size offset math and
+        // out-of-order delivery.
+        let mut pending = HashMap::new();
+        let packets = [
+            leader(1, 10),
+            payload_packet(1, 1, &[4, 5, 6, 7]),
+            trailer(1),
+            payload_packet(1, 2, &[8, 9]),
+            payload_packet(1, 0, &[0, 1, 2, 3]),
+        ];
+
+        let mut result = None;
+        for raw in &packets {
+            let header = parse_header(raw).unwrap();
+            if let Some(payload) = GvspReceiver::apply_packet(&mut pending, &header, raw) {
+                result = Some(payload);
+            }
+        }
+
+        let payload = result.expect("block should complete once the last packet arrives");
+        assert_eq!(payload.data, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn trailer_before_last_payload_packet_does_not_finalize_early() {
+        let mut pending = HashMap::new();
+        let packets = [leader(1, 8), payload_packet(1, 0, &[1, 2, 3, 4]), trailer(1)];
+
+        for raw in &packets {
+            let header = parse_header(raw).unwrap();
+            assert!(GvspReceiver::apply_packet(&mut pending, &header, raw).is_none());
+        }
+        assert!(pending.contains_key(&1));
+    }
+}
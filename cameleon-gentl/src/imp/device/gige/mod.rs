@@ -0,0 +1,87 @@
+//! GigE Vision `Device` backend.
+//!
+//! A [`GigEDevice`] owns the GVCP control channel used for bootstrap
+//! register access and, once streaming starts, the GVSP socket the
+//! [`gvsp::GvspReceiver`] reassembles frames from.
+
+mod gvsp;
+
+use std::net::{Ipv4Addr, UdpSocket};
+use std::time::Duration;
+
+use super::Device;
+use crate::imp::interface::gige::gvcp::{GvcpCodec, GVCP_PORT};
+use crate::GenTlResult;
+
+const CONTROL_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A device reachable over the network, speaking GVCP for control and
+/// GVSP for streaming.
+pub(crate) struct GigEDevice {
+    device_id: String,
+    ip_addr: Ipv4Addr,
+    control: Option<GvcpCodec>,
+    stream: Option<gvsp::GvspReceiver>,
+}
+
+impl GigEDevice {
+    pub(crate) fn new(device_id: String, ip_addr: Ipv4Addr) -> Self {
+        Self {
+            device_id,
+            ip_addr,
+            control: None,
+            stream: None,
+        }
+    }
+
+    fn control_mut(&mut self) -> GenTlResult<&mut GvcpCodec> {
+        self.control.as_mut().ok_or(crate::GenTlError::NotOpen)
+    }
+
+    pub(crate) fn read_reg(&mut self, address: u32) -> GenTlResult<u32> {
+        self.control_mut()?.read_reg(address)
+    }
+
+    pub(crate) fn write_reg(&mut self, address: u32, value: u32) -> GenTlResult<()> {
+        self.control_mut()?.write_reg(address, value)
+    }
+
+    /// Opens the GVSP socket and starts reassembling incoming frames.
+    /// `stream_port` is the local port the device was told (via its
+    /// `GevSCPHostPort` register) to send payloads to.
+    pub(crate) fn start_streaming(&mut self, stream_port: u16) -> GenTlResult<()> {
+        let socket =
+            UdpSocket::bind(("0.0.0.0", stream_port)).map_err(|_| crate::GenTlError::Io)?;
+        self.stream = Some(gvsp::GvspReceiver::new(socket));
+        Ok(())
+    }
+
+    pub(crate) fn recv_payload(&mut self) -> GenTlResult<gvsp::StreamPayload> {
+        self.stream
+            .as_mut()
+            .ok_or(crate::GenTlError::NotOpen)?
+            .recv_payload()
+    }
+}
+
+impl Device for GigEDevice {
+    fn open(&mut self) -> GenTlResult<()> {
+        let socket =
+            UdpSocket::bind(("0.0.0.0", 0)).map_err(|_| crate::GenTlError::Io)?;
+        socket
+            .connect((self.ip_addr, GVCP_PORT))
+            .map_err(|_| crate::GenTlError::Io)?;
+        self.control = Some(GvcpCodec::new(socket, CONTROL_TIMEOUT)?);
+        Ok(())
+    }
+
+    fn close(&mut self) -> GenTlResult<()> {
+        self.control = None;
+        self.stream = None;
+        Ok(())
+    }
+
+    fn device_id(&self) -> &str {
+        &self.device_id
+    }
+}
@@ -1,5 +1,6 @@
 use crate::GenTlResult;
 
+pub(crate) mod gige;
 pub(crate) mod u3v;
 
 mod u3v_memory;
@@ -0,0 +1,25 @@
+//! Hotplug notifications emitted by an [`super::Interface`], so a
+//! long-running consumer can react to a camera being plugged, unplugged,
+//! or changing accessibility instead of polling `enumerate_cameras()` in
+//! a loop.
+
+use crate::imp::device::DeviceAccessStatus;
+
+/// One hotplug notification. Every variant carries the `device_id` the
+/// event is about so a supervisor can match it against its own open
+/// handles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DeviceEvent {
+    /// A previously-unseen device started responding to discovery.
+    Arrived { device_id: String },
+
+    /// A device that used to respond to discovery stopped doing so.
+    Departed { device_id: String },
+
+    /// A known device's [`DeviceAccessStatus`] changed, e.g. from `Busy`
+    /// to `ReadWrite` once another application released it.
+    AccessStatusChanged {
+        device_id: String,
+        status: DeviceAccessStatus,
+    },
+}
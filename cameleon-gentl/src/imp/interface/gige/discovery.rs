@@ -0,0 +1,150 @@
+//! GVCP device discovery: broadcast `DISCOVERY_CMD` and collect the
+//! `DISCOVERY_ACK` replies sent back by every reachable GigE Vision device.
+
+use std::net::{Ipv4Addr, UdpSocket};
+use std::time::Duration;
+
+use super::gvcp::{GvcpCodec, GVCP_PORT};
+use crate::imp::device::DeviceAccessStatus;
+use crate::GenTlResult;
+
+const DISCOVERY_CMD: u16 = 0x0002;
+const DISCOVERY_ACK: u16 = 0x0003;
+const DISCOVERY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Bootstrap address of the Control Channel Privilege register every
+/// GigE Vision device exposes; reading it is how a client tells whether
+/// the device is free, already controlled, or unreachable for control.
+const CCP_REGISTER_ADDRESS: u32 = 0x0a00;
+const CCP_EXCLUSIVE_ACCESS: u32 = 1 << 0;
+const CCP_CONTROL_ACCESS: u32 = 1 << 1;
+// `GvcpCodec` retries a request up to 3 times on top of the first
+// attempt, so this bounds each device's probe to ~200ms total rather than
+// the ~2s a 500ms timeout would take, keeping a subnet full of unresponsive devices from making
+// `discover_devices` fall behind `hotplug::POLL_INTERVAL`.
+const CCP_PROBE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Information extracted from a single `DISCOVERY_ACK` reply.
+#[derive(Debug, Clone)]
+pub(crate) struct DiscoveredDevice {
+    pub(crate) mac_addr: [u8; 6],
+    pub(crate) ip_addr: Ipv4Addr,
+    pub(crate) subnet_mask: Ipv4Addr,
+    pub(crate) gateway_addr: Ipv4Addr,
+    pub(crate) manufacturer_name: String,
+    pub(crate) model_name: String,
+    pub(crate) device_id: String,
+    pub(crate) access_status: DeviceAccessStatus,
+}
+
+fn discovery_packet() -> [u8; 8] {
+    let mut packet = [0u8; 8];
+    packet[0] = 0x42;
+    packet[1] = 0x11; // Flag: acknowledge required, broadcast allowed.
+    packet[2..4].copy_from_slice(&DISCOVERY_CMD.to_be_bytes());
+    // Length and request id are both zero for a discovery broadcast.
+    packet
+}
+
+fn read_str(buf: &[u8], offset: usize, len: usize) -> String {
+    buf.get(offset..offset + len)
+        .map(|s| {
+            let end = s.iter().position(|&b| b == 0).unwrap_or(s.len());
+            String::from_utf8_lossy(&s[..end]).into_owned()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_ack(buf: &[u8]) -> Option<DiscoveredDevice> {
+    if buf.len() < 8 {
+        return None;
+    }
+    let ack_code = u16::from_be_bytes([buf[2], buf[3]]);
+    if ack_code != DISCOVERY_ACK {
+        return None;
+    }
+    let body = &buf[8..];
+    if body.len() < 248 {
+        return None;
+    }
+
+    let mac_addr = [body[10], body[11], body[12], body[13], body[14], body[15]];
+    let ip_addr = Ipv4Addr::new(body[24], body[25], body[26], body[27]);
+    let subnet_mask = Ipv4Addr::new(body[36], body[37], body[38], body[39]);
+    let gateway_addr = Ipv4Addr::new(body[44], body[45], body[46], body[47]);
+    let manufacturer_name = read_str(body, 48, 32);
+    let model_name = read_str(body, 80, 32);
+    let device_id = read_str(body, 144, 16);
+
+    Some(DiscoveredDevice {
+        mac_addr,
+        ip_addr,
+        subnet_mask,
+        gateway_addr,
+        manufacturer_name,
+        model_name,
+        device_id,
+        // Filled in by `probe_access_status` once discovery has
+        // collected every reply; a bare discovery ack alone doesn't
+        // carry the device's current access status.
+        access_status: DeviceAccessStatus::Unknown,
+    })
+}
+
+/// Reads the device's Control Channel Privilege register to tell whether
+/// it is free to open, already controlled by someone else, or unreachable
+/// for control even though it answered discovery.
+fn probe_access_status(ip_addr: Ipv4Addr) -> DeviceAccessStatus {
+    let Ok(socket) = UdpSocket::bind(("0.0.0.0", 0)) else {
+        return DeviceAccessStatus::Unknown;
+    };
+    if socket.connect((ip_addr, GVCP_PORT)).is_err() {
+        return DeviceAccessStatus::Unknown;
+    }
+    let Ok(mut codec) = GvcpCodec::new(socket, CCP_PROBE_TIMEOUT) else {
+        return DeviceAccessStatus::Unknown;
+    };
+
+    match codec.read_reg(CCP_REGISTER_ADDRESS) {
+        Ok(ccp) if ccp & (CCP_EXCLUSIVE_ACCESS | CCP_CONTROL_ACCESS) != 0 => {
+            DeviceAccessStatus::Busy
+        }
+        Ok(_) => DeviceAccessStatus::ReadWrite,
+        // Answered discovery but its control channel doesn't respond:
+        // reachable on the network but not controllable right now.
+        Err(_) => DeviceAccessStatus::NoAccess,
+    }
+}
+
+/// Broadcasts a `DISCOVERY_CMD` on the local subnet and collects every
+/// `DISCOVERY_ACK` that arrives before `DISCOVERY_TIMEOUT` elapses.
+pub(crate) fn discover_devices() -> GenTlResult<Vec<DiscoveredDevice>> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).map_err(|_| crate::GenTlError::Io)?;
+    socket.set_broadcast(true).map_err(|_| crate::GenTlError::Io)?;
+    socket
+        .set_read_timeout(Some(DISCOVERY_TIMEOUT))
+        .map_err(|_| crate::GenTlError::Io)?;
+
+    socket
+        .send_to(&discovery_packet(), (Ipv4Addr::BROADCAST, GVCP_PORT))
+        .map_err(|_| crate::GenTlError::Io)?;
+
+    let mut devices = Vec::new();
+    let mut buf = [0u8; 576];
+    loop {
+        match socket.recv_from(&mut buf) {
+            Ok((n, _from)) => {
+                if let Some(device) = parse_ack(&buf[..n]) {
+                    devices.push(device);
+                }
+            }
+            Err(_) => break, // Timed out: discovery window is over.
+        }
+    }
+
+    for device in &mut devices {
+        device.access_status = probe_access_status(device.ip_addr);
+    }
+
+    Ok(devices)
+}
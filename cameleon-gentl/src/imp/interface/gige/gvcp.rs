@@ -0,0 +1,296 @@
+//! GVCP (GigE Vision Control Protocol) packet codec.
+//!
+//! GVCP is a request/ack protocol carried over UDP on port 3956. Every
+//! request carries a 16-bit request id that the device echoes back in its
+//! ack so a client can match replies and retransmit on timeout.
+
+use std::io;
+use std::net::UdpSocket;
+use std::time::Duration;
+
+use crate::GenTlResult;
+
+/// Well-known UDP port used by both GVCP control and discovery.
+pub(crate) const GVCP_PORT: u16 = 3956;
+
+const DISCOVERY_CMD: u16 = 0x0002;
+const DISCOVERY_ACK: u16 = 0x0003;
+const READREG_CMD: u16 = 0x0080;
+const READREG_ACK: u16 = 0x0081;
+const WRITEREG_CMD: u16 = 0x0082;
+const WRITEREG_ACK: u16 = 0x0083;
+const READMEM_CMD: u16 = 0x0084;
+const READMEM_ACK: u16 = 0x0085;
+const WRITEMEM_CMD: u16 = 0x0086;
+const WRITEMEM_ACK: u16 = 0x0087;
+
+/// Status code carried in every GVCP ack header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GvcpStatus {
+    Success,
+    NotImplemented,
+    InvalidParameter,
+    InvalidAddress,
+    WriteProtect,
+    BadAlignment,
+    AccessDenied,
+    Busy,
+    Error(u16),
+}
+
+impl GvcpStatus {
+    fn from_code(code: u16) -> Self {
+        match code {
+            0x0000 => Self::Success,
+            0x8001 => Self::NotImplemented,
+            0x8002 => Self::InvalidParameter,
+            0x8003 => Self::InvalidAddress,
+            0x8004 => Self::WriteProtect,
+            0x8005 => Self::BadAlignment,
+            0x8007 => Self::AccessDenied,
+            0x800a => Self::Busy,
+            other => Self::Error(other),
+        }
+    }
+
+    fn is_success(self) -> bool {
+        matches!(self, Self::Success)
+    }
+}
+
+/// A single GVCP command, ready to be serialized onto the wire.
+#[derive(Debug, Clone)]
+pub(crate) enum GvcpCommand {
+    ReadReg { address: u32 },
+    WriteReg { address: u32, value: u32 },
+    ReadMem { address: u32, len: u16 },
+    WriteMem { address: u32, data: Vec<u8> },
+    Discovery,
+}
+
+impl GvcpCommand {
+    fn cmd_code(&self) -> u16 {
+        match self {
+            Self::ReadReg { .. } => READREG_CMD,
+            Self::WriteReg { .. } => WRITEREG_CMD,
+            Self::ReadMem { .. } => READMEM_CMD,
+            Self::WriteMem { .. } => WRITEMEM_CMD,
+            Self::Discovery => DISCOVERY_CMD,
+        }
+    }
+
+    fn encode(&self, request_id: u16) -> Vec<u8> {
+        let mut payload = Vec::new();
+        match self {
+            Self::ReadReg { address } => payload.extend_from_slice(&address.to_be_bytes()),
+            Self::WriteReg { address, value } => {
+                payload.extend_from_slice(&address.to_be_bytes());
+                payload.extend_from_slice(&value.to_be_bytes());
+            }
+            Self::ReadMem { address, len } => {
+                payload.extend_from_slice(&address.to_be_bytes());
+                // Reserved + length, padded to a 32-bit boundary.
+                payload.extend_from_slice(&[0, 0]);
+                payload.extend_from_slice(&len.to_be_bytes());
+            }
+            Self::WriteMem { address, data } => {
+                payload.extend_from_slice(&address.to_be_bytes());
+                payload.extend_from_slice(data);
+            }
+            Self::Discovery => {}
+        }
+
+        let mut packet = Vec::with_capacity(8 + payload.len());
+        packet.push(0x42); // Message prefix, fixed per the GVCP spec.
+        packet.push(0x01); // Flag: acknowledge required.
+        packet.extend_from_slice(&self.cmd_code().to_be_bytes());
+        #[allow(clippy::cast_possible_truncation)]
+        packet.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&request_id.to_be_bytes());
+        packet.extend_from_slice(&payload);
+        packet
+    }
+}
+
+/// A decoded GVCP ack, with the status and command-specific payload kept
+/// separate from the 8-byte header.
+pub(crate) struct GvcpAck {
+    pub(crate) status: GvcpStatus,
+    pub(crate) ack_code: u16,
+    pub(crate) request_id: u16,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl GvcpAck {
+    fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() < 8 {
+            return None;
+        }
+        let status = GvcpStatus::from_code(u16::from_be_bytes([buf[0], buf[1]]));
+        let ack_code = u16::from_be_bytes([buf[2], buf[3]]);
+        let len = u16::from_be_bytes([buf[4], buf[5]]) as usize;
+        let request_id = u16::from_be_bytes([buf[6], buf[7]]);
+        let payload = buf.get(8..8 + len)?.to_vec();
+        Some(Self {
+            status,
+            ack_code,
+            request_id,
+            payload,
+        })
+    }
+}
+
+/// Codec driving request/ack exchanges over a GVCP control socket,
+/// including retransmission on timeout.
+pub(crate) struct GvcpCodec {
+    socket: UdpSocket,
+    next_request_id: u16,
+    retry_count: u32,
+}
+
+impl GvcpCodec {
+    pub(crate) fn new(socket: UdpSocket, timeout: Duration) -> GenTlResult<Self> {
+        socket
+            .set_read_timeout(Some(timeout))
+            .map_err(Self::map_io_err)?;
+        Ok(Self {
+            socket,
+            next_request_id: 1,
+            retry_count: 3,
+        })
+    }
+
+    fn map_io_err(_e: io::Error) -> crate::GenTlError {
+        crate::GenTlError::Io
+    }
+
+    fn alloc_request_id(&mut self) -> u16 {
+        let id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1).max(1);
+        id
+    }
+
+    /// Sends `cmd` and waits for its ack, retransmitting up to
+    /// `self.retry_count` times if no reply arrives within the socket's
+    /// read timeout.
+    pub(crate) fn request(&mut self, cmd: &GvcpCommand) -> GenTlResult<GvcpAck> {
+        let request_id = self.alloc_request_id();
+        let packet = cmd.encode(request_id);
+
+        let mut last_err = None;
+        for _ in 0..=self.retry_count {
+            self.socket.send(&packet).map_err(Self::map_io_err)?;
+
+            let mut buf = [0u8; 576];
+            loop {
+                match self.socket.recv(&mut buf) {
+                    Ok(n) => {
+                        if let Some(ack) = GvcpAck::decode(&buf[..n]) {
+                            if ack.request_id == request_id {
+                                if !ack.status.is_success() {
+                                    return Err(crate::GenTlError::Io);
+                                }
+                                return Ok(ack);
+                            }
+                            // Stale ack for a previous, already-abandoned
+                            // request id: keep waiting within this attempt.
+                        }
+                    }
+                    Err(e) => {
+                        last_err = Some(e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Err(last_err.map_or(crate::GenTlError::Io, Self::map_io_err))
+    }
+
+    pub(crate) fn read_reg(&mut self, address: u32) -> GenTlResult<u32> {
+        let ack = self.request(&GvcpCommand::ReadReg { address })?;
+        let bytes: [u8; 4] = ack
+            .payload
+            .get(..4)
+            .and_then(|s| s.try_into().ok())
+            .ok_or(crate::GenTlError::Io)?;
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    pub(crate) fn write_reg(&mut self, address: u32, value: u32) -> GenTlResult<()> {
+        self.request(&GvcpCommand::WriteReg { address, value })?;
+        Ok(())
+    }
+
+    pub(crate) fn read_mem(&mut self, address: u32, len: u16) -> GenTlResult<Vec<u8>> {
+        let ack = self.request(&GvcpCommand::ReadMem { address, len })?;
+        Ok(ack.payload)
+    }
+
+    pub(crate) fn write_mem(&mut self, address: u32, data: Vec<u8>) -> GenTlResult<()> {
+        self.request(&GvcpCommand::WriteMem { address, data })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_read_reg() {
+        let packet = GvcpCommand::ReadReg { address: 0x1234 }.encode(0x0007);
+        assert_eq!(
+            packet,
+            vec![0x42, 0x01, 0x00, 0x80, 0x00, 0x04, 0x00, 0x07, 0x00, 0x00, 0x12, 0x34]
+        );
+    }
+
+    #[test]
+    fn encode_write_reg() {
+        let packet = GvcpCommand::WriteReg {
+            address: 0x1000,
+            value: 42,
+        }
+        .encode(1);
+        assert_eq!(packet[2..4], WRITEREG_CMD.to_be_bytes());
+        assert_eq!(packet[4..6], 8u16.to_be_bytes()); // Payload length.
+        assert_eq!(packet[6..8], 1u16.to_be_bytes()); // Request id.
+        assert_eq!(packet[8..12], 0x1000u32.to_be_bytes());
+        assert_eq!(packet[12..16], 42u32.to_be_bytes());
+    }
+
+    #[test]
+    fn decode_ack_round_trips_request_id_and_payload() {
+        let mut buf = vec![0x00, 0x00]; // Success.
+        buf.extend_from_slice(&READREG_ACK.to_be_bytes());
+        buf.extend_from_slice(&4u16.to_be_bytes()); // Payload length.
+        buf.extend_from_slice(&0x0007u16.to_be_bytes()); // Request id.
+        buf.extend_from_slice(&0xdead_beefu32.to_be_bytes());
+
+        let ack = GvcpAck::decode(&buf).unwrap();
+        assert_eq!(ack.status, GvcpStatus::Success);
+        assert_eq!(ack.ack_code, READREG_ACK);
+        assert_eq!(ack.request_id, 0x0007);
+        assert_eq!(ack.payload, 0xdead_beefu32.to_be_bytes());
+    }
+
+    #[test]
+    fn decode_ack_rejects_truncated_payload() {
+        let mut buf = vec![0x00, 0x00];
+        buf.extend_from_slice(&READREG_ACK.to_be_bytes());
+        buf.extend_from_slice(&4u16.to_be_bytes()); // Claims 4 bytes of payload...
+        buf.extend_from_slice(&0u16.to_be_bytes());
+        // ...but only 2 are actually present.
+        buf.extend_from_slice(&[0xaa, 0xbb]);
+
+        assert!(GvcpAck::decode(&buf).is_none());
+    }
+
+    #[test]
+    fn status_from_code_maps_known_error_codes() {
+        assert_eq!(GvcpStatus::from_code(0x8007), GvcpStatus::AccessDenied);
+        assert_eq!(GvcpStatus::from_code(0x1234), GvcpStatus::Error(0x1234));
+        assert!(GvcpStatus::from_code(0x0000).is_success());
+    }
+}
@@ -0,0 +1,156 @@
+//! GigE Vision has no hardware hotplug signal, so arrival/departure is
+//! inferred by periodically re-running GVCP discovery and diffing the
+//! result against what was seen last poll.
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use super::discovery::{self, DiscoveredDevice};
+use crate::imp::device::DeviceAccessStatus;
+use crate::imp::interface::events::DeviceEvent;
+use crate::GenTlResult;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+fn diff(
+    previous: &HashMap<String, DeviceAccessStatus>,
+    current: &[DiscoveredDevice],
+) -> Vec<DeviceEvent> {
+    let mut events = Vec::new();
+    let current_ids: HashMap<&str, DeviceAccessStatus> = current
+        .iter()
+        .map(|d| (d.device_id.as_str(), d.access_status))
+        .collect();
+
+    for device in current {
+        match previous.get(&device.device_id) {
+            None => events.push(DeviceEvent::Arrived {
+                device_id: device.device_id.clone(),
+            }),
+            Some(&status) if status != device.access_status => {
+                events.push(DeviceEvent::AccessStatusChanged {
+                    device_id: device.device_id.clone(),
+                    status: device.access_status,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    for device_id in previous.keys() {
+        if !current_ids.contains_key(device_id.as_str()) {
+            events.push(DeviceEvent::Departed {
+                device_id: device_id.clone(),
+            });
+        }
+    }
+
+    events
+}
+
+/// Spawns a background thread that re-runs discovery every
+/// [`POLL_INTERVAL`] and forwards the resulting [`DeviceEvent`]s.
+pub(crate) fn subscribe() -> GenTlResult<crossbeam_channel::Receiver<DeviceEvent>> {
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    thread::spawn(move || {
+        let mut previous = HashMap::new();
+        loop {
+            let Ok(current) = discovery::discover_devices() else {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            };
+
+            for event in diff(&previous, &current) {
+                if tx.send(event).is_err() {
+                    return; // Receiver dropped: nothing left to notify.
+                }
+            }
+
+            previous = current
+                .into_iter()
+                .map(|d| (d.device_id, d.access_status))
+                .collect();
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    Ok(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(id: &str, status: DeviceAccessStatus) -> DiscoveredDevice {
+        DiscoveredDevice {
+            mac_addr: [0; 6],
+            ip_addr: "0.0.0.0".parse().unwrap(),
+            subnet_mask: "0.0.0.0".parse().unwrap(),
+            gateway_addr: "0.0.0.0".parse().unwrap(),
+            manufacturer_name: String::new(),
+            model_name: String::new(),
+            device_id: id.to_string(),
+            access_status: status,
+        }
+    }
+
+    #[test]
+    fn diff_reports_arrived_for_a_previously_unseen_device() {
+        let previous = HashMap::new();
+        let current = [device("cam0", DeviceAccessStatus::ReadWrite)];
+
+        let events = diff(&previous, &current);
+
+        assert_eq!(
+            events,
+            vec![DeviceEvent::Arrived {
+                device_id: "cam0".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_reports_departed_for_a_device_missing_from_the_current_poll() {
+        let mut previous = HashMap::new();
+        previous.insert("cam0".to_string(), DeviceAccessStatus::ReadWrite);
+        let current = [];
+
+        let events = diff(&previous, &current);
+
+        assert_eq!(
+            events,
+            vec![DeviceEvent::Departed {
+                device_id: "cam0".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_reports_access_status_changed_when_status_differs() {
+        let mut previous = HashMap::new();
+        previous.insert("cam0".to_string(), DeviceAccessStatus::Busy);
+        let current = [device("cam0", DeviceAccessStatus::ReadWrite)];
+
+        let events = diff(&previous, &current);
+
+        assert_eq!(
+            events,
+            vec![DeviceEvent::AccessStatusChanged {
+                device_id: "cam0".to_string(),
+                status: DeviceAccessStatus::ReadWrite,
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_reports_nothing_when_a_device_is_unchanged() {
+        let mut previous = HashMap::new();
+        previous.insert("cam0".to_string(), DeviceAccessStatus::ReadWrite);
+        let current = [device("cam0", DeviceAccessStatus::ReadWrite)];
+
+        assert!(diff(&previous, &current).is_empty());
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{imp::port::*, GenTlResult};
 
+pub(crate) mod events;
+pub(crate) mod gige;
 pub(crate) mod u3v;
 
 mod u3v_memory;
 
+pub(crate) use events::DeviceEvent;
+
 // TODO: Add device related functions.
 pub(crate) trait Interface: Port {
     fn open(&mut self) -> GenTlResult<()>;
@@ -25,4 +29,21 @@ pub(crate) trait Interface: Port {
     fn subnet_mask(&self) -> Option<std::net::Ipv4Addr>;
 
     fn gateway_addr(&self) -> Option<std::net::Ipv4Addr>;
+
+    /// Subscribes to device arrival/departure/access-status-change
+    /// notifications on this interface, so a long-running consumer can
+    /// react to hotplug events instead of re-polling
+    /// `enumerate_cameras()`. Each call returns a fresh receiver; events
+    /// that occurred before subscribing are not replayed.
+    ///
+    /// `gige` (see `gige::GigEInterface::subscribe_events`) overrides
+    /// this by polling discovery acks, since GigE Vision has no hardware
+    /// hotplug signal of its own. `u3v` should override it too, backed by
+    /// a libusb hotplug callback — its `Interface` impl isn't part of
+    /// this source tree, so that override can't be added here; any
+    /// transport that doesn't override this default gets a normal error
+    /// instead of the `todo!()` panic this used to be.
+    fn subscribe_events(&mut self) -> GenTlResult<crossbeam_channel::Receiver<DeviceEvent>> {
+        Err(crate::GenTlError::Io)
+    }
 }
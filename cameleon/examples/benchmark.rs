@@ -7,6 +7,7 @@
 //! 2. Calculate the frame rate
 //! 3. Report the frame rate to stdout every 1 second
 
+use cameleon::clock::{Clocks, SystemClocks};
 use cameleon::u3v::enumerate_cameras;
 use std::time::{Duration, Instant};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -30,8 +31,15 @@ fn main() {
     camera.open().unwrap();
     camera.load_context().unwrap();
 
+    let clocks: Arc<dyn Clocks> = Arc::new(SystemClocks::new());
     let payload_rx = camera.start_streaming(3).unwrap();
     let mut frame_count = 0;
+    let mut jitter_total = Duration::ZERO;
+    let mut jitter_samples = 0u32;
+    let mut last_inter_arrival: Option<Duration> = None;
+    let mut missed_blocks = 0u64;
+    let mut last_id = None;
+    let mut last_arrival = None;
     let mut start_time = Instant::now();
 
     loop {
@@ -43,6 +51,31 @@ fn main() {
         match payload_rx.recv_blocking() {
             Ok(payload) => {
                 frame_count += 1;
+                // Prefer the transport-reported inter-arrival time, but
+                // fall back to our own clock so jitter is still tracked
+                // against a transport that doesn't stamp payloads itself.
+                let now = clocks.monotonic();
+                let inter_arrival = payload
+                    .inter_arrival()
+                    .or_else(|| last_arrival.map(|last| now.saturating_sub(last)));
+                // Jitter is how much each inter-arrival time differs from
+                // the one before it.
+                if let Some(inter_arrival) = inter_arrival {
+                    if let Some(prev) = last_inter_arrival {
+                        jitter_total += if inter_arrival > prev {
+                            inter_arrival - prev
+                        } else {
+                            prev - inter_arrival
+                        };
+                        jitter_samples += 1;
+                    }
+                    last_inter_arrival = Some(inter_arrival);
+                }
+                last_arrival = Some(now);
+                if let Some(last_id) = last_id {
+                    missed_blocks += payload.id().saturating_sub(last_id).saturating_sub(1);
+                }
+                last_id = Some(payload.id());
                 payload_rx.send_back(payload);
             }
             Err(e) => {
@@ -52,8 +85,21 @@ fn main() {
 
         if start_time.elapsed() >= Duration::from_secs(1) {
             let elapsed = start_time.elapsed();
-            println!("FPS: {}", frame_count as f64 / elapsed.as_secs_f64());
+            let avg_jitter_ms = if jitter_samples > 0 {
+                jitter_total.as_secs_f64() * 1000.0 / f64::from(jitter_samples)
+            } else {
+                0.0
+            };
+            println!(
+                "FPS: {:.2}, avg inter-arrival jitter: {:.2}ms, missed blocks: {}",
+                frame_count as f64 / elapsed.as_secs_f64(),
+                avg_jitter_ms,
+                missed_blocks
+            );
             frame_count = 0;
+            jitter_total = Duration::ZERO;
+            jitter_samples = 0;
+            missed_blocks = 0;
             start_time = Instant::now();
         }
     }
@@ -1,37 +1,84 @@
 //! This module contains a unified C API that can be shared between Cameleon and Aravis.
 
-use libc::{c_double, c_int, c_void};
+use libc::{c_char, c_double, c_int, c_void};
+use std::ffi::{CStr, CString};
+use std::io;
 use std::ptr;
 use crate::u3v;
 use crate::u3v::ControlHandle;
 use crate::u3v::StreamHandle;
 use crate::camera::Camera;
+use crate::payload::Payload;
+use cameleon_genapi::control::{ControlList, ControlPort, ControlValue};
+
+/// Lets a camera's USB3 Vision control channel back a [`ControlList`]
+/// directly: reading/writing a feature is just reading/writing its
+/// resolved register address over the control channel.
+impl ControlPort for ControlHandle {
+    fn read(&mut self, address: u64, len: usize) -> io::Result<Vec<u8>> {
+        #[allow(clippy::cast_possible_truncation)]
+        self.read_mem(address, len as u16)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+    }
+
+    fn write(&mut self, address: u64, data: &[u8]) -> io::Result<()> {
+        self.write_mem(address, data)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))
+    }
+}
 
 /// Genicam opaque camera descriptor.
 #[repr(C)]
 pub struct GenicamCamera {
     camera: Camera<ControlHandle, StreamHandle>,
+    payload_rx: Option<crate::PayloadReceiver>,
+}
+
+/// Metadata describing a frame filled in by [`genicam_get_frame`].
+#[repr(C)]
+pub struct GenicamFrameMeta {
+    pub width: u32,
+    pub height: u32,
+    /// `PixelFormat` discriminant, as defined by `payload::image::PixelFormat`.
+    pub pixel_format: u32,
+}
+
+fn spec_matches(
+    camera: &Camera<ControlHandle, StreamHandle>,
+    width: *const c_int,
+    height: *const c_int,
+    fps: *const c_double,
+) -> bool {
+    unsafe {
+        if !width.is_null() && i32::from(camera.info.width) != *width {
+            return false;
+        }
+        if !height.is_null() && i32::from(camera.info.height) != *height {
+            return false;
+        }
+        if !fps.is_null() && (f64::from(camera.info.fps) - *fps).abs() > f64::EPSILON {
+            return false;
+        }
+    }
+    true
 }
 
 /// Try to pick up a Genicam camera best matching the provided specification.
 #[no_mangle]
 pub unsafe extern "C" fn genicam_new(vid: *const c_int, pid: *const c_int, width: *const c_int, height: *const c_int, fps: *const c_double) -> *mut c_void {
-    if vid.is_null() || pid.is_null() || width.is_null() || height.is_null() || fps.is_null() {
-        return ptr::null_mut();
-    }
-
     // Logic to find and initialize a Genicam camera matching the specification
     let cameras = u3v::enumerate_cameras().unwrap_or_default();
     let camera = cameras.into_iter().find(|c| {
         (vid.is_null() || i32::from(c.info.vid) == *vid) &&
-        (pid.is_null() || i32::from(c.info.pid) == *pid) //&&
-        //(width.is_null() || c.width == *width) &&
-        //(height.is_null() || c.height == *height) &&
-        //(fps.is_null() || c.fps == *fps)
+        (pid.is_null() || i32::from(c.info.pid) == *pid) &&
+        spec_matches(c, width, height, fps)
     });
 
     if let Some(camera) = camera {
-        let genicam_camera = Box::new(GenicamCamera { camera });
+        let genicam_camera = Box::new(GenicamCamera {
+            camera,
+            payload_rx: None,
+        });
         Box::into_raw(genicam_camera) as *mut c_void
     } else {
         ptr::null_mut()
@@ -43,7 +90,10 @@ pub unsafe extern "C" fn genicam_new(vid: *const c_int, pid: *const c_int, width
 pub extern "C" fn genicam_new_any() -> *mut c_void {
     let cameras = u3v::enumerate_cameras().unwrap_or_default();
     if let Some(camera) = cameras.into_iter().next() {
-        let genicam_camera = Box::new(GenicamCamera { camera });
+        let genicam_camera = Box::new(GenicamCamera {
+            camera,
+            payload_rx: None,
+        });
         Box::into_raw(genicam_camera) as *mut c_void
     } else {
         ptr::null_mut()
@@ -60,3 +110,231 @@ pub extern "C" fn genicam_release(genicam_camera: *mut c_void) {
         drop(Box::from_raw(genicam_camera as *mut GenicamCamera));
     }
 }
+
+/// Opens the camera's control channel and loads its GenICam node graph.
+/// Returns `0` on success, `-1` on failure or a null handle.
+#[no_mangle]
+pub unsafe extern "C" fn genicam_open(genicam_camera: *mut c_void) -> c_int {
+    let Some(genicam_camera) = (genicam_camera as *mut GenicamCamera).as_mut() else {
+        return -1;
+    };
+
+    if genicam_camera.camera.open().is_err() {
+        return -1;
+    }
+    if genicam_camera.camera.load_context().is_err() {
+        return -1;
+    }
+    0
+}
+
+/// Starts streaming with `num_buffers` payload buffers in flight.
+/// Returns `0` on success, `-1` on failure or a null handle.
+#[no_mangle]
+pub unsafe extern "C" fn genicam_start_streaming(
+    genicam_camera: *mut c_void,
+    num_buffers: c_int,
+) -> c_int {
+    let Some(genicam_camera) = (genicam_camera as *mut GenicamCamera).as_mut() else {
+        return -1;
+    };
+
+    #[allow(clippy::cast_sign_loss)]
+    match genicam_camera.camera.start_streaming(num_buffers.max(1) as usize) {
+        Ok(rx) => {
+            genicam_camera.payload_rx = Some(rx);
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+/// Copies the next streamed payload into `buf` (which must be at least
+/// `len` bytes) and fills in `out_meta`. Returns the number of bytes
+/// copied, or `-1` on error.
+///
+/// `timeout_ms` is currently unused: [`crate::PayloadReceiver`] only
+/// exposes a plain blocking receive, so this call blocks until a payload
+/// arrives or the stream errors out rather than giving up after
+/// `timeout_ms`. The parameter is kept so callers don't need an ABI
+/// change once a timed receive lands.
+#[no_mangle]
+pub unsafe extern "C" fn genicam_get_frame(
+    genicam_camera: *mut c_void,
+    buf: *mut u8,
+    len: usize,
+    _timeout_ms: u32,
+    out_meta: *mut GenicamFrameMeta,
+) -> c_int {
+    let Some(genicam_camera) = (genicam_camera as *mut GenicamCamera).as_mut() else {
+        return -1;
+    };
+    let Some(payload_rx) = genicam_camera.payload_rx.as_ref() else {
+        return -1;
+    };
+    if buf.is_null() {
+        return -1;
+    }
+
+    let Ok(payload) = payload_rx.recv_blocking() else {
+        return -1;
+    };
+
+    let copy_result = copy_payload_out(&payload, buf, len, out_meta);
+    payload_rx.send_back(payload);
+
+    copy_result
+}
+
+unsafe fn copy_payload_out(
+    payload: &Payload,
+    buf: *mut u8,
+    len: usize,
+    out_meta: *mut GenicamFrameMeta,
+) -> c_int {
+    let data = payload.data();
+    if data.len() > len {
+        return -1;
+    }
+
+    ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len());
+
+    if let Some(out_meta) = out_meta.as_mut() {
+        out_meta.width = payload.width();
+        out_meta.height = payload.height();
+        out_meta.pixel_format = payload.pixel_format() as u32;
+    }
+
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    (data.len() as c_int)
+}
+
+/// Converts a JSON value into the [`ControlValue`] a feature's type
+/// expects. There is no JSON representation for [`ControlValue::Command`]:
+/// triggering a command feature is spelled as `null` in the input object.
+///
+/// A JSON number is ambiguous between [`ControlValue::Integer`] and
+/// [`ControlValue::Float`] on its own (`5000` and `5000.0` should both set
+/// a `Float` feature) so when `current` — the feature's current value,
+/// read before the new one is parsed — is a `Float`, the number is parsed
+/// as a `Float`. Otherwise an integral number becomes an `Integer` and any
+/// other number a `Float`.
+fn json_to_control_value(value: &serde_json::Value, current: Option<&ControlValue>) -> Option<ControlValue> {
+    match value {
+        serde_json::Value::Null => Some(ControlValue::Command),
+        serde_json::Value::Bool(b) => Some(ControlValue::Bool(*b)),
+        serde_json::Value::String(s) => Some(ControlValue::String(s.clone())),
+        serde_json::Value::Number(n) => {
+            if matches!(current, Some(ControlValue::Float(_))) {
+                n.as_f64().map(ControlValue::Float)
+            } else if let Some(i) = n.as_i64() {
+                Some(ControlValue::Integer(i))
+            } else {
+                n.as_f64().map(ControlValue::Float)
+            }
+        }
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => None,
+    }
+}
+
+/// The inverse of [`json_to_control_value`]: a [`ControlValue::Command`]
+/// reads back as `null`, mirroring how it's written.
+fn control_value_to_json(value: &ControlValue) -> serde_json::Value {
+    match value {
+        ControlValue::Integer(v) | ControlValue::Enum(v) => serde_json::Value::from(*v),
+        ControlValue::Float(v) => serde_json::Value::from(*v),
+        ControlValue::Bool(v) => serde_json::Value::from(*v),
+        ControlValue::String(v) => serde_json::Value::from(v.clone()),
+        ControlValue::Command => serde_json::Value::Null,
+    }
+}
+
+/// Applies a JSON object of feature name -> value pairs (e.g.
+/// `{"ExposureTime": 5000.0, "Gain": 2.0}`) through the typed control
+/// layer ([`ControlList`], see `cameleon_genapi::control`). Returns `0`
+/// on success, `-1` if the JSON is malformed or any feature fails to set.
+#[no_mangle]
+pub unsafe extern "C" fn genicam_set_features(
+    genicam_camera: *mut c_void,
+    json: *const c_char,
+) -> c_int {
+    let Some(genicam_camera) = (genicam_camera as *mut GenicamCamera).as_mut() else {
+        return -1;
+    };
+    if json.is_null() {
+        return -1;
+    }
+    let Ok(json) = CStr::from_ptr(json).to_str() else {
+        return -1;
+    };
+    let Ok(serde_json::Value::Object(features)) = serde_json::from_str(json) else {
+        return -1;
+    };
+
+    let mut controls = ControlList::new(
+        genicam_camera.camera.node_store_mut(),
+        genicam_camera.camera.control_handle_mut(),
+    );
+    for (name, value) in features {
+        let current = controls.get(&name).ok();
+        let Some(value) = json_to_control_value(&value, current.as_ref()) else {
+            return -1;
+        };
+        if controls.set(&name, value).is_err() {
+            return -1;
+        }
+    }
+    0
+}
+
+/// Reads back every feature named in the JSON array `json` (e.g.
+/// `["ExposureTime", "Gain"]`) through the typed control layer
+/// ([`ControlList`], see `cameleon_genapi::control`), returning a
+/// newly-allocated JSON object string the caller must free with
+/// `genicam_free_string`, or `NULL` on error.
+#[no_mangle]
+pub unsafe extern "C" fn genicam_get_features(
+    genicam_camera: *mut c_void,
+    json: *const c_char,
+) -> *mut c_char {
+    let Some(genicam_camera) = (genicam_camera as *mut GenicamCamera).as_mut() else {
+        return ptr::null_mut();
+    };
+    if json.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(json) = CStr::from_ptr(json).to_str() else {
+        return ptr::null_mut();
+    };
+    let Ok(serde_json::Value::Array(names)) = serde_json::from_str(json) else {
+        return ptr::null_mut();
+    };
+
+    let mut controls = ControlList::new(
+        genicam_camera.camera.node_store_mut(),
+        genicam_camera.camera.control_handle_mut(),
+    );
+    let mut result = serde_json::Map::new();
+    for name in names {
+        let Some(name) = name.as_str() else {
+            return ptr::null_mut();
+        };
+        let Ok(value) = controls.get(name) else {
+            return ptr::null_mut();
+        };
+        result.insert(name.to_string(), control_value_to_json(&value));
+    }
+
+    match CString::new(serde_json::Value::Object(result).to_string()) {
+        Ok(s) => s.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees a string returned by [`genicam_get_features`].
+#[no_mangle]
+pub unsafe extern "C" fn genicam_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,208 @@
+//! A minimal DNG (Digital Negative) writer, modeled on libcamera's
+//! `dng_writer`: just enough TIFF/DNG structure to save a raw Bayer
+//! mosaic losslessly, with the tags a downstream DNG reader needs to
+//! interpret it (CFA pattern, black level, a color matrix).
+
+use std::io::{self, Write};
+
+use super::image::{BayerPattern, Image};
+
+const TAG_IMAGE_WIDTH: u16 = 0x0100;
+const TAG_IMAGE_LENGTH: u16 = 0x0101;
+const TAG_BITS_PER_SAMPLE: u16 = 0x0102;
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_PHOTOMETRIC_INTERPRETATION: u16 = 0x0106;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_SAMPLES_PER_PIXEL: u16 = 0x0115;
+const TAG_ROWS_PER_STRIP: u16 = 0x0116;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+const TAG_CFA_REPEAT_PATTERN_DIM: u16 = 0x828d;
+const TAG_CFA_PATTERN: u16 = 0x828e;
+const TAG_DNG_VERSION: u16 = 0xc612;
+const TAG_BLACK_LEVEL: u16 = 0xc61a;
+const TAG_WHITE_LEVEL: u16 = 0xc61d;
+const TAG_COLOR_MATRIX1: u16 = 0xc621;
+
+const TYPE_BYTE: u16 = 1;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_SRATIONAL: u16 = 10;
+
+struct IfdEntry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    /// Either the inline value (left-justified per TIFF rules) or, if the
+    /// entry doesn't fit in 4 bytes, the offset patched in later.
+    value: Vec<u8>,
+}
+
+fn cfa_order(pattern: BayerPattern) -> [u8; 4] {
+    // 0 = Red, 1 = Green, 2 = Blue, per the TIFF/EP CFAPattern values.
+    match pattern {
+        BayerPattern::Rggb => [0, 1, 1, 2],
+        BayerPattern::Gbrg => [1, 2, 0, 1],
+        BayerPattern::Grbg => [1, 0, 2, 1],
+        BayerPattern::Bggr => [2, 1, 1, 0],
+    }
+}
+
+/// Writes `image` (which must be a raw, undecoded Bayer mosaic) as a
+/// single-strip, uncompressed DNG to `out`.
+pub fn write_dng<W: Write>(out: &mut W, image: &Image) -> io::Result<()> {
+    let pattern = image
+        .pixel_format
+        .bayer_pattern()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a Bayer pixel format"))?;
+    let bits = image.pixel_format.bits_per_sample();
+
+    let strip: Vec<u8> = image.samples.iter().flat_map(|&s| s.to_le_bytes()).collect();
+
+    let header_len = 8u32;
+    // The IFD immediately follows the 8-byte header.
+    let ifd_offset = header_len;
+
+    let mut entries = vec![
+        IfdEntry {
+            tag: TAG_IMAGE_WIDTH,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: image.width.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_IMAGE_LENGTH,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: image.height.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_BITS_PER_SAMPLE,
+            field_type: TYPE_SHORT,
+            count: 1,
+            // Every sample is written to the strip as a 16-bit word; the
+            // real sensor bit depth is carried by `WhiteLevel` below.
+            value: 16u16.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_COMPRESSION,
+            field_type: TYPE_SHORT,
+            count: 1,
+            value: 1u16.to_le_bytes().to_vec(), // Uncompressed.
+        },
+        IfdEntry {
+            tag: TAG_PHOTOMETRIC_INTERPRETATION,
+            field_type: TYPE_SHORT,
+            count: 1,
+            value: 32803u16.to_le_bytes().to_vec(), // CFA.
+        },
+        IfdEntry {
+            tag: TAG_SAMPLES_PER_PIXEL,
+            field_type: TYPE_SHORT,
+            count: 1,
+            value: 1u16.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_ROWS_PER_STRIP,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: image.height.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_STRIP_BYTE_COUNTS,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: (strip.len() as u32).to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_CFA_REPEAT_PATTERN_DIM,
+            field_type: TYPE_SHORT,
+            count: 2,
+            value: [2u16, 2u16].iter().flat_map(|v| v.to_le_bytes()).collect(),
+        },
+        IfdEntry {
+            tag: TAG_CFA_PATTERN,
+            field_type: TYPE_BYTE,
+            count: 4,
+            value: cfa_order(pattern).to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_DNG_VERSION,
+            field_type: TYPE_BYTE,
+            count: 4,
+            value: vec![1, 4, 0, 0],
+        },
+        IfdEntry {
+            tag: TAG_BLACK_LEVEL,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: 0u32.to_le_bytes().to_vec(),
+        },
+        IfdEntry {
+            tag: TAG_WHITE_LEVEL,
+            field_type: TYPE_LONG,
+            count: 1,
+            value: (u32::from(1u32 << bits) - 1).to_le_bytes().to_vec(),
+        },
+        // An identity-ish matrix: good enough for the file to round-trip
+        // losslessly; real color calibration is out of scope here.
+        IfdEntry {
+            tag: TAG_COLOR_MATRIX1,
+            field_type: TYPE_SRATIONAL,
+            count: 9,
+            value: identity_color_matrix(),
+        },
+    ];
+    entries.sort_by_key(|e| e.tag);
+
+    // Strip offsets entry depends on the laid-out size of everything
+    // before it, so it's computed once the rest of the IFD is known.
+    let ifd_entry_count = entries.len() as u16 + 1;
+    let ifd_size = 2 + u32::from(ifd_entry_count) * 12 + 4;
+    let mut extra_data = Vec::new();
+    for entry in &mut entries {
+        if entry.value.len() > 4 {
+            let offset = ifd_offset + ifd_size + extra_data.len() as u32;
+            extra_data.extend_from_slice(&entry.value);
+            entry.value = offset.to_le_bytes().to_vec();
+        } else {
+            entry.value.resize(4, 0);
+        }
+    }
+    let strip_offset = ifd_offset + ifd_size + extra_data.len() as u32;
+    entries.push(IfdEntry {
+        tag: TAG_STRIP_OFFSETS,
+        field_type: TYPE_LONG,
+        count: 1,
+        value: strip_offset.to_le_bytes().to_vec(),
+    });
+    entries.sort_by_key(|e| e.tag);
+
+    // TIFF header: little-endian, magic 42, offset of the first IFD.
+    out.write_all(b"II")?;
+    out.write_all(&42u16.to_le_bytes())?;
+    out.write_all(&ifd_offset.to_le_bytes())?;
+
+    out.write_all(&(entries.len() as u16).to_le_bytes())?;
+    for entry in &entries {
+        out.write_all(&entry.tag.to_le_bytes())?;
+        out.write_all(&entry.field_type.to_le_bytes())?;
+        out.write_all(&entry.count.to_le_bytes())?;
+        out.write_all(&entry.value)?;
+    }
+    out.write_all(&0u32.to_le_bytes())?; // No next IFD.
+
+    out.write_all(&extra_data)?;
+    out.write_all(&strip)?;
+
+    Ok(())
+}
+
+fn identity_color_matrix() -> Vec<u8> {
+    let mut out = Vec::with_capacity(9 * 8);
+    for i in 0..9 {
+        let numerator: i32 = if i % 4 == 0 { 1 } else { 0 };
+        out.extend_from_slice(&numerator.to_le_bytes());
+        out.extend_from_slice(&1i32.to_le_bytes());
+    }
+    out
+}
@@ -0,0 +1,222 @@
+//! Decodes a [`super::Payload`]'s raw bytes according to its GenICam
+//! `PixelFormat` into a normalized, unpacked buffer.
+//!
+//! Packed formats (e.g. `Mono12Packed`, which fits two 12-bit pixels into
+//! three bytes) are unpacked explicitly rather than left for the caller to
+//! puzzle out.
+
+/// The subset of the GenICam `PixelFormat` enumeration this crate knows
+/// how to decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    Mono8,
+    Mono10,
+    Mono12Packed,
+    BayerRG8,
+    BayerGB8,
+    BayerGR8,
+    BayerBG8,
+    BayerRG10,
+    BayerGB10,
+    BayerGR10,
+    BayerBG10,
+    BayerRG12,
+    BayerGB12,
+    BayerGR12,
+    BayerBG12,
+    Rgb8,
+    Yuv422,
+}
+
+/// Bayer color-filter-array arrangement, independent of bit depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BayerPattern {
+    Rggb,
+    Gbrg,
+    Grbg,
+    Bggr,
+}
+
+impl PixelFormat {
+    #[must_use]
+    pub fn bayer_pattern(self) -> Option<BayerPattern> {
+        match self {
+            Self::BayerRG8 | Self::BayerRG10 | Self::BayerRG12 => Some(BayerPattern::Rggb),
+            Self::BayerGB8 | Self::BayerGB10 | Self::BayerGB12 => Some(BayerPattern::Gbrg),
+            Self::BayerGR8 | Self::BayerGR10 | Self::BayerGR12 => Some(BayerPattern::Grbg),
+            Self::BayerBG8 | Self::BayerBG10 | Self::BayerBG12 => Some(BayerPattern::Bggr),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn bits_per_sample(self) -> u8 {
+        match self {
+            Self::Mono8
+            | Self::BayerRG8
+            | Self::BayerGB8
+            | Self::BayerGR8
+            | Self::BayerBG8
+            | Self::Rgb8
+            | Self::Yuv422 => 8,
+            Self::Mono10
+            | Self::BayerRG10
+            | Self::BayerGB10
+            | Self::BayerGR10
+            | Self::BayerBG10 => 10,
+            Self::Mono12Packed
+            | Self::BayerRG12
+            | Self::BayerGB12
+            | Self::BayerGR12
+            | Self::BayerBG12 => 12,
+        }
+    }
+
+    #[must_use]
+    pub fn samples_per_pixel(self) -> u8 {
+        match self {
+            Self::Rgb8 => 3,
+            Self::Yuv422 => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A decoded image: one `u16` sample per pixel component, regardless of
+/// the wire bit depth, so callers never have to think about packing.
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: PixelFormat,
+    pub samples: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    TruncatedPayload,
+    UnsupportedFormat,
+}
+
+/// Decodes `data`, a buffer laid out per `pixel_format`, into an [`Image`].
+pub fn decode(
+    pixel_format: PixelFormat,
+    width: u32,
+    height: u32,
+    data: &[u8],
+) -> Result<Image, DecodeError> {
+    let samples = match pixel_format {
+        PixelFormat::Mono8
+        | PixelFormat::BayerRG8
+        | PixelFormat::BayerGB8
+        | PixelFormat::BayerGR8
+        | PixelFormat::BayerBG8
+        | PixelFormat::Rgb8
+        | PixelFormat::Yuv422 => unpack_8bit(data),
+        PixelFormat::Mono10
+        | PixelFormat::BayerRG10
+        | PixelFormat::BayerGB10
+        | PixelFormat::BayerGR10
+        | PixelFormat::BayerBG10 => unpack_16bit_le(data, 10)?,
+        PixelFormat::Mono12Packed
+        | PixelFormat::BayerRG12
+        | PixelFormat::BayerGB12
+        | PixelFormat::BayerGR12
+        | PixelFormat::BayerBG12 => unpack_12packed(data)?,
+    };
+
+    let expected = width as usize * height as usize * pixel_format.samples_per_pixel() as usize;
+    if samples.len() < expected {
+        return Err(DecodeError::TruncatedPayload);
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixel_format,
+        samples,
+    })
+}
+
+fn unpack_8bit(data: &[u8]) -> Vec<u16> {
+    data.iter().map(|&b| u16::from(b)).collect()
+}
+
+/// `Mono10`/`BayerXX10` pixels are stored two bytes per sample,
+/// little-endian, with the value in the low `bits` bits.
+fn unpack_16bit_le(data: &[u8], bits: u32) -> Result<Vec<u16>, DecodeError> {
+    if data.len() % 2 != 0 {
+        return Err(DecodeError::TruncatedPayload);
+    }
+    let mask = (1u16 << bits) - 1;
+    Ok(data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]) & mask)
+        .collect())
+}
+
+/// `Mono12Packed` stores two 12-bit pixels in three bytes:
+/// `[p0_lo_8, p1_lo_4 | p0_hi_4, p1_hi_8]`.
+fn unpack_12packed(data: &[u8]) -> Result<Vec<u16>, DecodeError> {
+    if data.len() % 3 != 0 {
+        return Err(DecodeError::TruncatedPayload);
+    }
+    let mut out = Vec::with_capacity(data.len() / 3 * 2);
+    for chunk in data.chunks_exact(3) {
+        let p0 = u16::from(chunk[0]) | (u16::from(chunk[1] & 0x0f) << 8);
+        let p1 = (u16::from(chunk[1]) >> 4) | (u16::from(chunk[2]) << 4);
+        out.push(p0);
+        out.push(p1);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_8bit_passes_bytes_through() {
+        assert_eq!(unpack_8bit(&[0, 1, 255]), vec![0, 1, 255]);
+    }
+
+    #[test]
+    fn unpack_16bit_le_masks_to_the_requested_bit_depth() {
+        // 0x03ff little-endian, masked to 10 bits: unaffected.
+        let samples = unpack_16bit_le(&[0xff, 0x03], 10).unwrap();
+        assert_eq!(samples, vec![0x03ff]);
+
+        // 0xffff masked to 10 bits keeps only the low 10 bits.
+        let samples = unpack_16bit_le(&[0xff, 0xff], 10).unwrap();
+        assert_eq!(samples, vec![0x03ff]);
+    }
+
+    #[test]
+    fn unpack_16bit_le_rejects_odd_length() {
+        assert_eq!(
+            unpack_16bit_le(&[0x00], 10),
+            Err(DecodeError::TruncatedPayload)
+        );
+    }
+
+    #[test]
+    fn unpack_12packed_splits_two_pixels_from_three_bytes() {
+        // [p0_lo_8, p1_lo_4 | p0_hi_4, p1_hi_8] = [0x01, 0x20, 0x00]
+        // decodes to p0 = 0x001, p1 = 0x002.
+        let samples = unpack_12packed(&[0x01, 0x20, 0x00]).unwrap();
+        assert_eq!(samples, vec![0x001, 0x002]);
+    }
+
+    #[test]
+    fn unpack_12packed_rejects_length_not_a_multiple_of_three() {
+        assert_eq!(
+            unpack_12packed(&[0x00, 0x00]),
+            Err(DecodeError::TruncatedPayload)
+        );
+    }
+
+    #[test]
+    fn decode_reports_truncated_payload_when_fewer_samples_than_the_image_needs() {
+        let result = decode(PixelFormat::Mono8, 4, 4, &[0; 4]);
+        assert_eq!(result.unwrap_err(), DecodeError::TruncatedPayload);
+    }
+}
@@ -0,0 +1,127 @@
+//! Payloads delivered by a streaming camera, and ways to turn their raw
+//! bytes into something a caller can actually look at or save.
+
+use std::time::Duration;
+
+pub mod dng;
+pub mod image;
+
+/// One payload delivered by [`crate::camera::Camera::start_streaming`],
+/// recycled back to the stream via `send_back` once the caller is done
+/// with it.
+pub struct Payload {
+    id: u64,
+    pixel_format: image::PixelFormat,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+    capture_time: Duration,
+    device_timestamp: Option<u64>,
+    inter_arrival: Option<Duration>,
+}
+
+impl Payload {
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u64,
+        pixel_format: image::PixelFormat,
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+        capture_time: Duration,
+        device_timestamp: Option<u64>,
+        inter_arrival: Option<Duration>,
+    ) -> Self {
+        Self {
+            id,
+            pixel_format,
+            width,
+            height,
+            data,
+            capture_time,
+            device_timestamp,
+            inter_arrival,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    #[must_use]
+    pub fn pixel_format(&self) -> image::PixelFormat {
+        self.pixel_format
+    }
+
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// [`crate::clock::Clocks::monotonic`] time at which this payload
+    /// finished being captured.
+    #[must_use]
+    pub fn capture_time(&self) -> Duration {
+        self.capture_time
+    }
+
+    /// The device's own block/timestamp register value, when the
+    /// transport exposes one (e.g. GVSP's trailer timestamp).
+    #[must_use]
+    pub fn device_timestamp(&self) -> Option<u64> {
+        self.device_timestamp
+    }
+
+    /// Time elapsed since the previous payload was delivered on this
+    /// stream, or `None` for the first payload.
+    #[must_use]
+    pub fn inter_arrival(&self) -> Option<Duration> {
+        self.inter_arrival
+    }
+
+    /// Builds a payload stamped with `clocks`' current monotonic time,
+    /// computing [`Self::inter_arrival`] against `previous_capture_time`
+    /// (the [`Self::capture_time`] of the payload delivered just before
+    /// this one on the same stream, or `None` for the first payload).
+    ///
+    /// A capture path should construct every delivered [`Payload`] this
+    /// way rather than with [`Self::new`] directly, so capture-time and
+    /// jitter accounting go through one place regardless of transport.
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn captured_now(
+        clocks: &dyn crate::clock::Clocks,
+        previous_capture_time: Option<Duration>,
+        id: u64,
+        pixel_format: image::PixelFormat,
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+        device_timestamp: Option<u64>,
+    ) -> Self {
+        let capture_time = clocks.monotonic();
+        let inter_arrival = previous_capture_time.map(|prev| capture_time.saturating_sub(prev));
+        Self::new(
+            id,
+            pixel_format,
+            width,
+            height,
+            data,
+            capture_time,
+            device_timestamp,
+            inter_arrival,
+        )
+    }
+}